@@ -3,6 +3,13 @@ use bitflags::bitflags;
 /// The negative sign for a 2's complement, 8-bit integer
 const NEGATIVE_SIGN_U8: u8 = 0b10000000;
 
+/// Address of the low byte of the NMI vector (`$FFFA`/`$FFFB`).
+pub const NMI_VECTOR: u16 = 0xfffa;
+/// Address of the low byte of the reset vector (`$FFFC`/`$FFFD`).
+pub const RESET_VECTOR: u16 = 0xfffc;
+/// Address of the low byte of the IRQ/BRK vector (`$FFFE`/`$FFFF`).
+pub const IRQ_VECTOR: u16 = 0xfffe;
+
 bitflags! {
     /// An 8-bit register that holds all the 6502 flags.
     pub struct StatusRegister: u8 {
@@ -26,6 +33,8 @@ pub struct Registers {
     pub x: u8,
     /// The Y index
     pub y: u8,
+    /// The stack pointer, an offset into the stack page (`$0100`-`$01FF`)
+    pub s: u8,
     /// The status register with all flags
     pub flags: StatusRegister,
     /// The program counter or instruction pointer
@@ -39,6 +48,7 @@ impl Registers {
             a: 0,
             x: 0,
             y: 0,
+            s: 0,
             flags: StatusRegister::empty(),
             pc: 0,
         }
@@ -49,6 +59,7 @@ impl Registers {
         self.a = 0;
         self.x = 0;
         self.y = 0;
+        self.s = 0;
         self.flags = StatusRegister::empty();
         self.pc = 0;
     }
@@ -61,15 +72,31 @@ impl Registers {
 /// writing to memory.
 pub trait MemoryBus {
     /// Absolute indexed mode.
-    /// 
+    ///
     /// Either register X or Y can be used for `idx`.
     fn abs_idx(&self, address: u16, idx: u8) -> u16 {
-        address.overflowing_add(idx as u16).0
+        self.abs_idx_carry(address, idx).0
+    }
+
+    /// Absolute indexed mode, also reporting whether the index carried into the
+    /// high byte (i.e. a page boundary was crossed).
+    ///
+    /// The execution layer uses the carry to add the documented one-cycle
+    /// page-crossing penalty.
+    fn abs_idx_carry(&self, address: u16, idx: u8) -> (u16, bool) {
+        let result = address.overflowing_add(idx as u16).0;
+        let crossed = address & 0xff00 != result & 0xff00;
+        (result, crossed)
     }
 
-    /// Absolute indirect mode.
-    /// 
-    /// Only used for the JMP instruction.
+    /// Absolute indirect mode, with the NMOS page-wrap bug.
+    ///
+    /// Only used for the JMP instruction. On NMOS parts, a pointer whose low
+    /// byte is `$FF` fetches the high byte from `$xx00` rather than the start of
+    /// the next page. Use [`abs_indirect_fixed`] for the corrected 65C02
+    /// behavior.
+    ///
+    /// [`abs_indirect_fixed`]: MemoryBus::abs_indirect_fixed
     fn abs_indirect(&self, address: u16) -> u16 {
         let hi_addr = if address & 0xff == 0xff {
             address & 0xff00
@@ -83,6 +110,20 @@ pub trait MemoryBus {
         u16::from_le_bytes([lo, hi])
     }
 
+    /// Absolute indirect mode, without the NMOS page-wrap bug.
+    ///
+    /// The 65C02 fixed the JMP-indirect bug: the high byte is always fetched
+    /// from `address + 1`, crossing the page boundary when the low byte is
+    /// `$FF`. See [`abs_indirect`] for the buggy NMOS behavior.
+    ///
+    /// [`abs_indirect`]: MemoryBus::abs_indirect
+    fn abs_indirect_fixed(&self, address: u16) -> u16 {
+        let lo = self.read(address);
+        let hi = self.read(address.overflowing_add(1).0);
+
+        u16::from_le_bytes([lo, hi])
+    }
+
     /// X-Indexed, Zero-Page Indirect mode.
     fn indirect_x(&self, address: u8, x: u8) -> u16 {
         let address = address.overflowing_add(x).0;
@@ -93,6 +134,15 @@ pub trait MemoryBus {
 
     /// Zero-Page Indirect, Y-Indexed mode.
     fn indirect_y(&self, address: u8, y: u8) -> u16 {
+        self.indirect_y_carry(address, y).0
+    }
+
+    /// Zero-Page Indirect, Y-Indexed mode, also reporting whether the index
+    /// carried into the high byte (i.e. a page boundary was crossed).
+    ///
+    /// The execution layer uses the carry to add the documented one-cycle
+    /// page-crossing penalty.
+    fn indirect_y_carry(&self, address: u8, y: u8) -> (u16, bool) {
         let (lo, carry) = self.read(address as u16).overflowing_add(y);
         let hi = {
             let value = self.read(address.overflowing_add(1).0 as u16);
@@ -103,6 +153,16 @@ pub trait MemoryBus {
             }
         };
 
+        (u16::from_le_bytes([lo, hi]), carry)
+    }
+
+    /// Zero-Page Indirect mode.
+    ///
+    /// This unindexed mode is a 65C02 CMOS addition: the 16-bit pointer is read
+    /// from `address` and `address + 1` in the zero page, wrapping within it.
+    fn zero_indirect(&self, address: u8) -> u16 {
+        let lo = self.read(address as u16);
+        let hi = self.read(address.overflowing_add(1).0 as u16);
         u16::from_le_bytes([lo, hi])
     }
 
@@ -112,12 +172,276 @@ pub trait MemoryBus {
     /// Write a byte to the 16-bit address bus.
     fn write(&mut self, address: u16, value: u8);
 
+    /// Loads a raw binary image into memory starting at `base`.
+    ///
+    /// This is the convenience entry point for loading test ROMs such as Klaus
+    /// Dormann's functional test suite. Bytes that would wrap past `$FFFF` are
+    /// truncated.
+    fn load(&mut self, base: u16, data: &[u8]) {
+        for (offset, &byte) in data.iter().enumerate() {
+            let address = match u16::try_from(offset)
+                .ok()
+                .and_then(|offset| base.checked_add(offset))
+            {
+                Some(address) => address,
+                None => break,
+            };
+            self.write(address, byte);
+        }
+    }
+
     /// Zero Page mode.
     fn zero_idx(&self, address: u8, idx: u8) -> u16 {
         address.overflowing_add(idx).0 as u16
     }
 }
 
+/// A 6502 model variant, selecting the behavioral quirks that differ between
+/// real parts.
+///
+/// The instruction set is shared between every 6502, but the original NMOS
+/// part, the Revision A (which lacks the ROR instruction), the NES' 2A03 (which
+/// has no decimal mode), and the 65C02 (CMOS, with extra instructions and a
+/// handful of fixed bugs) each behave slightly differently. Each model is a
+/// zero-sized type implementing this trait, selected as the
+/// [`InstructionExecution::Variant`] associated type so the whole instruction
+/// set adapts without reimplementing every method.
+pub trait Variant {
+    /// Whether the ROR instruction is present.
+    ///
+    /// The earliest Revision A parts shipped without it.
+    fn has_ror() -> bool {
+        true
+    }
+
+    /// Whether ADC/SBC honor the decimal (BCD) flag.
+    ///
+    /// The NES' 2A03 has the decimal circuitry disabled.
+    fn decimal_enabled() -> bool {
+        true
+    }
+
+    /// Whether the 65C02 CMOS instructions and addressing modes are available.
+    fn cmos_extensions() -> bool {
+        false
+    }
+}
+
+/// The original NMOS 6502, with decimal mode and the JMP-indirect page-wrap bug.
+pub struct Nmos;
+
+impl Variant for Nmos {}
+
+/// An early Revision A NMOS 6502, identical to [`Nmos`] but without ROR.
+pub struct RevisionA;
+
+impl Variant for RevisionA {
+    fn has_ror() -> bool {
+        false
+    }
+}
+
+/// An NMOS 6502 with decimal mode disabled, as found in the NES' 2A03.
+pub struct NoDecimal;
+
+impl Variant for NoDecimal {
+    fn decimal_enabled() -> bool {
+        false
+    }
+}
+
+/// The 65C02 CMOS part, with the extra instructions and fixed quirks.
+pub struct Cmos;
+
+impl Variant for Cmos {
+    fn cmos_extensions() -> bool {
+        true
+    }
+}
+
+/// The addressing mode an opcode uses to find its operand.
+///
+/// These map onto the addressing helpers on [`MemoryBus`]; [`step`] reads the
+/// operand bytes that follow an opcode according to the mode before dispatching
+/// to the matching [`InstructionExecution`] method.
+///
+/// [`step`]: InstructionExecution::step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressingMode {
+    /// No operand.
+    Implied,
+    /// The accumulator is the operand.
+    Accumulator,
+    /// A single immediate byte.
+    Immediate,
+    /// A signed branch offset relative to the program counter.
+    Relative,
+    /// A zero-page address.
+    ZeroPage,
+    /// A zero-page address, X-indexed.
+    ZeroPageX,
+    /// A zero-page address, Y-indexed.
+    ZeroPageY,
+    /// A 16-bit absolute address.
+    Absolute,
+    /// A 16-bit absolute address, X-indexed.
+    AbsoluteX,
+    /// A 16-bit absolute address, Y-indexed.
+    AbsoluteY,
+    /// A 16-bit indirect address (JMP only).
+    Indirect,
+    /// An X-indexed, zero-page indirect address.
+    IndirectX,
+    /// A zero-page indirect, Y-indexed address.
+    IndirectY,
+    /// A zero-page indirect address (CMOS).
+    ZeroPageIndirect,
+}
+
+/// An instruction, independent of its addressing mode.
+///
+/// Only the instructions implemented by [`InstructionExecution`] have variants;
+/// every other opcode decodes to [`Instruction::Unknown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// ADC - add with carry.
+    Adc,
+    /// STZ - store zero (CMOS).
+    Stz,
+    /// BRA - branch always (CMOS).
+    Bra,
+    /// PHX - push X (CMOS).
+    Phx,
+    /// PHY - push Y (CMOS).
+    Phy,
+    /// PLX - pull X (CMOS).
+    Plx,
+    /// PLY - pull Y (CMOS).
+    Ply,
+    /// TRB - test and reset bits (CMOS).
+    Trb,
+    /// TSB - test and set bits (CMOS).
+    Tsb,
+    /// INC A - increment the accumulator (CMOS).
+    IncA,
+    /// DEC A - decrement the accumulator (CMOS).
+    DecA,
+    /// BIT - test bits (only the immediate mode is implemented, CMOS).
+    Bit,
+    /// BRK - force interrupt.
+    Brk,
+    /// JMP - jump (only the indirect mode is implemented).
+    Jmp,
+    /// An opcode with no implementation in this crate yet.
+    Unknown,
+}
+
+/// Decodes an opcode byte into its instruction and addressing mode.
+///
+/// Every opcode not yet implemented by [`InstructionExecution`] decodes to
+/// [`Instruction::Unknown`] with [`AddressingMode::Implied`], which [`step`]
+/// treats as a no-op.
+///
+/// [`step`]: InstructionExecution::step
+pub fn decode(opcode: u8) -> (Instruction, AddressingMode) {
+    use AddressingMode::*;
+    use Instruction::*;
+
+    match opcode {
+        0x00 => (Brk, Implied),
+
+        // ADC
+        0x69 => (Adc, Immediate),
+        0x65 => (Adc, ZeroPage),
+        0x75 => (Adc, ZeroPageX),
+        0x6d => (Adc, Absolute),
+        0x7d => (Adc, AbsoluteX),
+        0x79 => (Adc, AbsoluteY),
+        0x61 => (Adc, IndirectX),
+        0x71 => (Adc, IndirectY),
+        0x72 => (Adc, ZeroPageIndirect),
+
+        // STZ
+        0x64 => (Stz, ZeroPage),
+        0x74 => (Stz, ZeroPageX),
+        0x9c => (Stz, Absolute),
+        0x9e => (Stz, AbsoluteX),
+
+        // Branch, stack, and accumulator increments/decrements
+        0x80 => (Bra, Relative),
+        0xda => (Phx, Implied),
+        0x5a => (Phy, Implied),
+        0xfa => (Plx, Implied),
+        0x7a => (Ply, Implied),
+        0x1a => (IncA, Accumulator),
+        0x3a => (DecA, Accumulator),
+
+        // TRB / TSB
+        0x14 => (Trb, ZeroPage),
+        0x1c => (Trb, Absolute),
+        0x04 => (Tsb, ZeroPage),
+        0x0c => (Tsb, Absolute),
+
+        // BIT immediate
+        0x89 => (Bit, Immediate),
+
+        // JMP indirect
+        0x6c => (Jmp, Indirect),
+
+        _ => (Unknown, Implied),
+    }
+}
+
+/// The base number of clock cycles an opcode consumes, before any
+/// page-crossing or taken-branch penalties.
+///
+/// Unimplemented opcodes report 2 cycles, the shortest instruction length.
+pub fn base_cycles(opcode: u8) -> u8 {
+    match opcode {
+        0x00 => 7,
+
+        // ADC
+        0x69 => 2,
+        0x65 => 3,
+        0x75 => 4,
+        0x6d => 4,
+        0x7d => 4,
+        0x79 => 4,
+        0x61 => 6,
+        0x71 => 5,
+        0x72 => 5,
+
+        // STZ
+        0x64 => 3,
+        0x74 => 4,
+        0x9c => 4,
+        0x9e => 5,
+
+        // Branch, stack, and accumulator increments/decrements
+        0x80 => 2,
+        0xda => 3,
+        0x5a => 3,
+        0xfa => 4,
+        0x7a => 4,
+        0x1a => 2,
+        0x3a => 2,
+
+        // TRB / TSB
+        0x14 => 5,
+        0x1c => 6,
+        0x04 => 5,
+        0x0c => 6,
+
+        // BIT immediate
+        0x89 => 2,
+
+        // JMP indirect
+        0x6c => 5,
+
+        _ => 2,
+    }
+}
+
 /// This trait defines the default 6502 instruction set.
 ///
 /// It includes default implementations for each instruction, but they can also be overridden if needed. For
@@ -148,36 +472,84 @@ pub trait MemoryBus {
 /// Functions that end in common, (`adc_common`) do not use any addressing mode. The common functions are
 /// used to share implementions between an instruction with different addressing modes.
 pub trait InstructionExecution {
+    /// The 6502 model this engine emulates, selecting model-specific quirks.
+    type Variant: Variant;
+
     /// Returns the memory bus connected to this execution engine.
     fn bus(&mut self) -> &mut dyn MemoryBus;
 
     /// Returns the registers connected to this execution engine.
     fn registers(&mut self) -> &mut Registers;
 
+    /// Whether this model honors the decimal (BCD) flag.
+    ///
+    /// Most NMOS parts perform packed BCD arithmetic in ADC/SBC when
+    /// [`StatusRegister::DECIMAL`] is set. Some variants (notably the NES' 2A03)
+    /// ignore the flag entirely and always work in binary; this simply defers
+    /// to the selected [`Variant`].
+    fn decimal_enabled(&self) -> bool {
+        Self::Variant::decimal_enabled()
+    }
+
     /// Common implementation for the ADC instruction.
     fn adc_common(&mut self, value: u8) {
+        let decimal = self.decimal_enabled();
         let registers = self.registers();
-        // TODO: Check decimal flag
 
-        // Perform addition as unsigned 32-bit integers
-        let result = registers.a as u32
-            + value as u32
-            + (registers.flags & StatusRegister::CARRY).bits() as u32;
+        let a = registers.a;
+        let carry = (registers.flags & StatusRegister::CARRY).bits();
+
+        // Binary addition as unsigned 32-bit integers. On NMOS the Z flag is
+        // always derived from this binary sum, even in decimal mode, so it is
+        // computed regardless of the flag.
+        let binary = a as u32 + value as u32 + carry as u32;
+        registers
+            .flags
+            .set(StatusRegister::ZERO, binary & u8::MAX as u32 == 0);
+
+        if decimal && registers.flags.contains(StatusRegister::DECIMAL) {
+            // Packed BCD addition, following the NMOS 6502 algorithm.
+            let mut al = (a & 0x0f) + (value & 0x0f) + carry;
+            if al >= 0x0a {
+                al = ((al + 0x06) & 0x0f) + 0x10;
+            }
+            let mut a_hi = (a as u16 & 0xf0) + (value as u16 & 0xf0) + al as u16;
+
+            // The NMOS quirk: N and V are decided from the intermediate sum,
+            // before the high-nibble decimal adjust.
+            registers.flags.set(
+                StatusRegister::NEGATIVE,
+                a_hi as u8 & NEGATIVE_SIGN_U8 == NEGATIVE_SIGN_U8,
+            );
+            let overflow = ((a ^ value) & NEGATIVE_SIGN_U8 == 0)
+                && ((a ^ a_hi as u8) & NEGATIVE_SIGN_U8 == NEGATIVE_SIGN_U8);
+            registers.flags.set(StatusRegister::OVERFLOW, overflow);
+
+            if a_hi >= 0xa0 {
+                a_hi += 0x60;
+                registers.flags.insert(StatusRegister::CARRY);
+            } else {
+                registers.flags.remove(StatusRegister::CARRY);
+            }
+
+            registers.a = (a_hi & u8::MAX as u16) as u8;
+            return;
+        }
 
         // Carry flag if result does not fit in an unsigned 8-bit integer
         registers
             .flags
-            .set(StatusRegister::CARRY, result > u8::MAX as u32);
+            .set(StatusRegister::CARRY, binary > u8::MAX as u32);
 
         // Truncate result to an unsigned 8-bit integer
-        let result = (result & u8::MAX as u32) as u8;
+        let result = (binary & u8::MAX as u32) as u8;
 
         // Overflow flag
         // This one is kinda complicated.
         // It is set if both operands have the same sign, but the result has the opposite or "incorrect" sign.
         // This could be (positive + positive = negative) or (negative + negative = positive).
-        let overflow = ((registers.a ^ value) & NEGATIVE_SIGN_U8 == 0)
-            && ((registers.a ^ result) & NEGATIVE_SIGN_U8 == NEGATIVE_SIGN_U8);
+        let overflow = ((a ^ value) & NEGATIVE_SIGN_U8 == 0)
+            && ((a ^ result) & NEGATIVE_SIGN_U8 == NEGATIVE_SIGN_U8);
         registers.flags.set(StatusRegister::OVERFLOW, overflow);
 
         // Negative flag if the result is negative (interpreted as 2's complement)
@@ -186,9 +558,6 @@ pub trait InstructionExecution {
             result & NEGATIVE_SIGN_U8 == NEGATIVE_SIGN_U8,
         );
 
-        // Zero flag if the result is 0
-        registers.flags.set(StatusRegister::ZERO, result == 0);
-
         // Set accumulator to result
         registers.a = result;
     }
@@ -241,4 +610,451 @@ pub trait InstructionExecution {
         let value = self.bus().read(indirect_addr);
         self.adc_common(value);
     }
+
+    /// Pushes a byte onto the stack, decrementing the stack pointer.
+    fn push(&mut self, value: u8) {
+        let s = self.registers().s;
+        self.bus().write(0x0100 + s as u16, value);
+        self.registers().s = s.overflowing_sub(1).0;
+    }
+
+    /// Pulls a byte off the stack, incrementing the stack pointer.
+    fn pull(&mut self) -> u8 {
+        let s = self.registers().s.overflowing_add(1).0;
+        self.registers().s = s;
+        self.bus().read(0x0100 + s as u16)
+    }
+
+    /// STZ - store zero.
+    ///
+    /// A 65C02 CMOS instruction; a no-op on variants without the CMOS
+    /// extensions.
+    fn stz(&mut self, address: u16) {
+        if !Self::Variant::cmos_extensions() {
+            return;
+        }
+        self.bus().write(address, 0);
+    }
+
+    /// BRA - branch always.
+    ///
+    /// A 65C02 CMOS instruction; a no-op on variants without the CMOS
+    /// extensions.
+    fn bra(&mut self, offset: i8) {
+        if !Self::Variant::cmos_extensions() {
+            return;
+        }
+        let registers = self.registers();
+        registers.pc = registers.pc.overflowing_add(offset as u16).0;
+    }
+
+    /// PHX - push the X index onto the stack.
+    ///
+    /// A 65C02 CMOS instruction; a no-op on variants without the CMOS
+    /// extensions.
+    fn phx(&mut self) {
+        if !Self::Variant::cmos_extensions() {
+            return;
+        }
+        let x = self.registers().x;
+        self.push(x);
+    }
+
+    /// PHY - push the Y index onto the stack.
+    ///
+    /// A 65C02 CMOS instruction; a no-op on variants without the CMOS
+    /// extensions.
+    fn phy(&mut self) {
+        if !Self::Variant::cmos_extensions() {
+            return;
+        }
+        let y = self.registers().y;
+        self.push(y);
+    }
+
+    /// PLX - pull the X index off the stack.
+    ///
+    /// A 65C02 CMOS instruction; a no-op on variants without the CMOS
+    /// extensions.
+    fn plx(&mut self) {
+        if !Self::Variant::cmos_extensions() {
+            return;
+        }
+        let value = self.pull();
+        let registers = self.registers();
+        registers.x = value;
+        registers.flags.set(StatusRegister::ZERO, value == 0);
+        registers.flags.set(
+            StatusRegister::NEGATIVE,
+            value & NEGATIVE_SIGN_U8 == NEGATIVE_SIGN_U8,
+        );
+    }
+
+    /// PLY - pull the Y index off the stack.
+    ///
+    /// A 65C02 CMOS instruction; a no-op on variants without the CMOS
+    /// extensions.
+    fn ply(&mut self) {
+        if !Self::Variant::cmos_extensions() {
+            return;
+        }
+        let value = self.pull();
+        let registers = self.registers();
+        registers.y = value;
+        registers.flags.set(StatusRegister::ZERO, value == 0);
+        registers.flags.set(
+            StatusRegister::NEGATIVE,
+            value & NEGATIVE_SIGN_U8 == NEGATIVE_SIGN_U8,
+        );
+    }
+
+    /// TSB - test and set bits.
+    ///
+    /// Sets the Z flag from `A & M`, then writes `M | A` back to memory. A
+    /// 65C02 CMOS instruction; a no-op on variants without the CMOS extensions.
+    fn tsb(&mut self, address: u16) {
+        if !Self::Variant::cmos_extensions() {
+            return;
+        }
+        let a = self.registers().a;
+        let value = self.bus().read(address);
+        self.registers()
+            .flags
+            .set(StatusRegister::ZERO, a & value == 0);
+        self.bus().write(address, value | a);
+    }
+
+    /// TRB - test and reset bits.
+    ///
+    /// Sets the Z flag from `A & M`, then writes `M & !A` back to memory. A
+    /// 65C02 CMOS instruction; a no-op on variants without the CMOS extensions.
+    fn trb(&mut self, address: u16) {
+        if !Self::Variant::cmos_extensions() {
+            return;
+        }
+        let a = self.registers().a;
+        let value = self.bus().read(address);
+        self.registers()
+            .flags
+            .set(StatusRegister::ZERO, a & value == 0);
+        self.bus().write(address, value & !a);
+    }
+
+    /// INC A - increment the accumulator.
+    ///
+    /// A 65C02 CMOS instruction; a no-op on variants without the CMOS
+    /// extensions.
+    fn inc_a(&mut self) {
+        if !Self::Variant::cmos_extensions() {
+            return;
+        }
+        let registers = self.registers();
+        let result = registers.a.overflowing_add(1).0;
+        registers.a = result;
+        registers.flags.set(StatusRegister::ZERO, result == 0);
+        registers.flags.set(
+            StatusRegister::NEGATIVE,
+            result & NEGATIVE_SIGN_U8 == NEGATIVE_SIGN_U8,
+        );
+    }
+
+    /// DEC A - decrement the accumulator.
+    ///
+    /// A 65C02 CMOS instruction; a no-op on variants without the CMOS
+    /// extensions.
+    fn dec_a(&mut self) {
+        if !Self::Variant::cmos_extensions() {
+            return;
+        }
+        let registers = self.registers();
+        let result = registers.a.overflowing_sub(1).0;
+        registers.a = result;
+        registers.flags.set(StatusRegister::ZERO, result == 0);
+        registers.flags.set(
+            StatusRegister::NEGATIVE,
+            result & NEGATIVE_SIGN_U8 == NEGATIVE_SIGN_U8,
+        );
+    }
+
+    /// BIT immediate.
+    ///
+    /// Unlike the memory addressing modes of BIT, the immediate form only
+    /// affects the Z flag. This mode is a 65C02 CMOS addition; a no-op on
+    /// variants without the CMOS extensions.
+    fn bit_imm(&mut self, value: u8) {
+        if !Self::Variant::cmos_extensions() {
+            return;
+        }
+        let registers = self.registers();
+        registers
+            .flags
+            .set(StatusRegister::ZERO, registers.a & value == 0);
+    }
+
+    /// BRK - force an interrupt.
+    ///
+    /// Pushes the program counter and status register, sets the interrupt
+    /// disable flag, and loads the program counter from the IRQ vector. On
+    /// CMOS parts the decimal flag is also cleared, which NMOS parts do not do.
+    fn brk(&mut self) {
+        // BRK is a two-byte instruction: the byte after the opcode is a
+        // signature the CPU skips, so the pushed return address is PC + 2. The
+        // step loop has already advanced past the opcode, leaving one more byte
+        // to skip here.
+        let pc = self.registers().pc.wrapping_add(1);
+        self.push((pc >> 8) as u8);
+        self.push(pc as u8);
+        let flags = (self.registers().flags | StatusRegister::B_FLAG).bits();
+        self.push(flags);
+
+        let registers = self.registers();
+        registers.flags.insert(StatusRegister::INTERRUPT);
+        if Self::Variant::cmos_extensions() {
+            registers.flags.remove(StatusRegister::DECIMAL);
+        }
+
+        let lo = self.bus().read(IRQ_VECTOR);
+        let hi = self.bus().read(IRQ_VECTOR + 1);
+        self.registers().pc = u16::from_le_bytes([lo, hi]);
+    }
+
+    /// Reads a 16-bit vector (little-endian) from the given address.
+    fn read_vector(&mut self, address: u16) -> u16 {
+        let lo = self.bus().read(address);
+        let hi = self.bus().read(address + 1);
+        u16::from_le_bytes([lo, hi])
+    }
+
+    /// Resets the CPU, clearing the registers and loading the program counter
+    /// from the reset vector at [`RESET_VECTOR`].
+    fn reset(&mut self) {
+        self.registers().reset();
+        let pc = self.read_vector(RESET_VECTOR);
+        self.registers().pc = pc;
+    }
+
+    /// Services a maskable interrupt, loading the program counter from the IRQ
+    /// vector at [`IRQ_VECTOR`].
+    ///
+    /// Does nothing if the interrupt disable flag is set.
+    fn irq(&mut self) {
+        if self.registers().flags.contains(StatusRegister::INTERRUPT) {
+            return;
+        }
+        let pc = self.registers().pc;
+        self.push((pc >> 8) as u8);
+        self.push(pc as u8);
+        let flags = self.registers().flags.bits();
+        self.push(flags);
+        self.registers().flags.insert(StatusRegister::INTERRUPT);
+        let pc = self.read_vector(IRQ_VECTOR);
+        self.registers().pc = pc;
+    }
+
+    /// Services a non-maskable interrupt, loading the program counter from the
+    /// NMI vector at [`NMI_VECTOR`].
+    fn nmi(&mut self) {
+        let pc = self.registers().pc;
+        self.push((pc >> 8) as u8);
+        self.push(pc as u8);
+        let flags = self.registers().flags.bits();
+        self.push(flags);
+        self.registers().flags.insert(StatusRegister::INTERRUPT);
+        let pc = self.read_vector(NMI_VECTOR);
+        self.registers().pc = pc;
+    }
+
+    /// Reads the byte at the program counter and advances it.
+    fn next_byte(&mut self) -> u8 {
+        let pc = self.registers().pc;
+        let value = self.bus().read(pc);
+        self.registers().pc = pc.overflowing_add(1).0;
+        value
+    }
+
+    /// Reads the little-endian word at the program counter and advances it past
+    /// both bytes.
+    fn next_word(&mut self) -> u16 {
+        let lo = self.next_byte();
+        let hi = self.next_byte();
+        u16::from_le_bytes([lo, hi])
+    }
+
+    /// JMP indirect.
+    ///
+    /// Reads the jump target through the pointer at `address`, selecting the
+    /// buggy NMOS page-wrap behavior ([`MemoryBus::abs_indirect`]) or the
+    /// corrected 65C02 behavior ([`MemoryBus::abs_indirect_fixed`]) according
+    /// to the CPU variant.
+    fn jmp_indirect(&mut self, address: u16) {
+        let target = if Self::Variant::cmos_extensions() {
+            self.bus().abs_indirect_fixed(address)
+        } else {
+            self.bus().abs_indirect(address)
+        };
+        self.registers().pc = target;
+    }
+
+    /// Runs the CPU until it traps, returning the program counter it trapped at.
+    ///
+    /// A "trap" is the self-loop (a branch or jump to itself) that functional
+    /// test programs such as Klaus Dormann's suite jump to on both success and
+    /// failure: when a [`step`] leaves the program counter unchanged, execution
+    /// can make no further progress and the final program counter identifies
+    /// the trap, letting the caller distinguish the success trap from a failing
+    /// one.
+    ///
+    /// [`step`]: InstructionExecution::step
+    fn run_until_trap(&mut self) -> u16 {
+        loop {
+            let pc = self.registers().pc;
+            self.step();
+            if self.registers().pc == pc {
+                return pc;
+            }
+        }
+    }
+
+    /// Performs one fetch-decode-execute cycle, returning the number of clock
+    /// cycles it consumed.
+    ///
+    /// Reads the opcode at the program counter, advances past it and its
+    /// operand bytes, and dispatches to the matching instruction method. The
+    /// cycle count is the opcode's [`base_cycles`] plus the documented
+    /// one-cycle penalty when an indexed read crosses a page boundary, one
+    /// cycle for a taken branch and one more if it crosses a page boundary, and
+    /// one cycle for JMP indirect on the 65C02. Opcodes with no implementation in this
+    /// crate ([`Instruction::Unknown`]) are treated as no-ops.
+    fn step(&mut self) -> u8 {
+        let opcode = self.next_byte();
+        let (instruction, mode) = decode(opcode);
+        let mut cycles = base_cycles(opcode);
+
+        match instruction {
+            Instruction::Adc => {
+                let value = match mode {
+                    AddressingMode::Immediate => self.next_byte(),
+                    AddressingMode::ZeroPage => {
+                        let address = self.next_byte();
+                        self.bus().read(address as u16)
+                    }
+                    AddressingMode::ZeroPageX => {
+                        let address = self.next_byte();
+                        let x = self.registers().x;
+                        let address = self.bus().zero_idx(address, x);
+                        self.bus().read(address)
+                    }
+                    AddressingMode::Absolute => {
+                        let address = self.next_word();
+                        self.bus().read(address)
+                    }
+                    AddressingMode::AbsoluteX => {
+                        let address = self.next_word();
+                        let x = self.registers().x;
+                        let (address, crossed) = self.bus().abs_idx_carry(address, x);
+                        if crossed {
+                            cycles += 1;
+                        }
+                        self.bus().read(address)
+                    }
+                    AddressingMode::AbsoluteY => {
+                        let address = self.next_word();
+                        let y = self.registers().y;
+                        let (address, crossed) = self.bus().abs_idx_carry(address, y);
+                        if crossed {
+                            cycles += 1;
+                        }
+                        self.bus().read(address)
+                    }
+                    AddressingMode::IndirectX => {
+                        let address = self.next_byte();
+                        let x = self.registers().x;
+                        let address = self.bus().indirect_x(address, x);
+                        self.bus().read(address)
+                    }
+                    AddressingMode::IndirectY => {
+                        let address = self.next_byte();
+                        let y = self.registers().y;
+                        let (address, crossed) = self.bus().indirect_y_carry(address, y);
+                        if crossed {
+                            cycles += 1;
+                        }
+                        self.bus().read(address)
+                    }
+                    AddressingMode::ZeroPageIndirect => {
+                        let address = self.next_byte();
+                        let address = self.bus().zero_indirect(address);
+                        self.bus().read(address)
+                    }
+                    _ => return cycles,
+                };
+                self.adc_common(value);
+            }
+            Instruction::Stz => {
+                let address = match mode {
+                    AddressingMode::ZeroPage => self.next_byte() as u16,
+                    AddressingMode::ZeroPageX => {
+                        let address = self.next_byte();
+                        let x = self.registers().x;
+                        self.bus().zero_idx(address, x)
+                    }
+                    AddressingMode::Absolute => self.next_word(),
+                    AddressingMode::AbsoluteX => {
+                        let address = self.next_word();
+                        let x = self.registers().x;
+                        self.bus().abs_idx(address, x)
+                    }
+                    _ => return cycles,
+                };
+                self.stz(address);
+            }
+            Instruction::Trb | Instruction::Tsb => {
+                let address = match mode {
+                    AddressingMode::ZeroPage => self.next_byte() as u16,
+                    AddressingMode::Absolute => self.next_word(),
+                    _ => return cycles,
+                };
+                match instruction {
+                    Instruction::Trb => self.trb(address),
+                    _ => self.tsb(address),
+                }
+            }
+            Instruction::Bra => {
+                let offset = self.next_byte() as i8;
+                let before = self.registers().pc;
+                self.bra(offset);
+                // A taken branch costs an extra cycle, and another if it
+                // crosses a page boundary. BRA is always taken on CMOS.
+                if Self::Variant::cmos_extensions() {
+                    let after = self.registers().pc;
+                    cycles += 1;
+                    if before & 0xff00 != after & 0xff00 {
+                        cycles += 1;
+                    }
+                }
+            }
+            Instruction::Bit => {
+                let value = self.next_byte();
+                self.bit_imm(value);
+            }
+            Instruction::Phx => self.phx(),
+            Instruction::Phy => self.phy(),
+            Instruction::Plx => self.plx(),
+            Instruction::Ply => self.ply(),
+            Instruction::IncA => self.inc_a(),
+            Instruction::DecA => self.dec_a(),
+            Instruction::Brk => self.brk(),
+            Instruction::Jmp => {
+                let address = self.next_word();
+                self.jmp_indirect(address);
+                // JMP (indirect) takes one extra cycle on the 65C02.
+                if Self::Variant::cmos_extensions() {
+                    cycles += 1;
+                }
+            }
+            Instruction::Unknown => {}
+        }
+
+        cycles
+    }
 }
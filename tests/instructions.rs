@@ -28,6 +28,25 @@ struct TestExecution {
 }
 
 impl InstructionExecution for TestExecution {
+    type Variant = Nmos;
+
+    fn bus(&mut self) -> &mut dyn MemoryBus {
+        &mut self.bus
+    }
+
+    fn registers(&mut self) -> &mut Registers {
+        &mut self.registers
+    }
+}
+
+struct CmosExecution {
+    pub bus: TestBus,
+    pub registers: Registers,
+}
+
+impl InstructionExecution for CmosExecution {
+    type Variant = Cmos;
+
     fn bus(&mut self) -> &mut dyn MemoryBus {
         &mut self.bus
     }
@@ -68,6 +87,42 @@ fn abs_indirect() {
     assert_eq!(bus.abs_indirect(0xabff), 0x1234);
 }
 
+#[test]
+fn abs_indirect_fixed() {
+    let mut bus = TestBus::new();
+
+    bus.memory[0xabff] = 0x34;
+    bus.memory[0xab00] = 0x12;
+    bus.memory[0xac00] = 0x56;
+
+    // The buggy variant wraps within the page; the fixed variant does not.
+    assert_eq!(bus.abs_indirect(0xabff), 0x1234);
+    assert_eq!(bus.abs_indirect_fixed(0xabff), 0x5634);
+}
+
+#[test]
+fn jmp_indirect_variant() {
+    let mut nmos = TestExecution {
+        bus: TestBus::new(),
+        registers: Registers::new(),
+    };
+    nmos.bus().write(0xabff, 0x34);
+    nmos.bus().write(0xab00, 0x12);
+    nmos.bus().write(0xac00, 0x56);
+    nmos.jmp_indirect(0xabff);
+    assert_eq!(nmos.registers.pc, 0x1234);
+
+    let mut cmos = CmosExecution {
+        bus: TestBus::new(),
+        registers: Registers::new(),
+    };
+    cmos.bus().write(0xabff, 0x34);
+    cmos.bus().write(0xab00, 0x12);
+    cmos.bus().write(0xac00, 0x56);
+    cmos.jmp_indirect(0xabff);
+    assert_eq!(cmos.registers.pc, 0x5634);
+}
+
 #[test]
 fn zero_idx() {
     let bus = TestBus::new();
@@ -131,6 +186,186 @@ fn indirect_y() {
     assert_eq!(bus.indirect_y(0xff, 0x21), 0x1255);
 }
 
+#[test]
+fn zero_indirect() {
+    let mut bus = TestBus::new();
+
+    assert_eq!(bus.zero_indirect(0), 0);
+
+    bus.memory[0x12] = 0xef;
+    bus.memory[0x13] = 0xbe;
+
+    assert_eq!(bus.zero_indirect(0x12), 0xbeef);
+
+    bus.memory[0xff] = 0x34;
+    bus.memory[0x00] = 0x12;
+
+    assert_eq!(bus.zero_indirect(0xff), 0x1234);
+}
+
+#[test]
+fn stz() {
+    let mut execution = CmosExecution {
+        bus: TestBus::new(),
+        registers: Registers::new(),
+    };
+
+    execution.bus().write(0x1234, 0xff);
+    execution.stz(0x1234);
+    assert_eq!(execution.bus().read(0x1234), 0);
+}
+
+#[test]
+fn trb_tsb() {
+    let mut execution = CmosExecution {
+        bus: TestBus::new(),
+        registers: Registers::new(),
+    };
+
+    execution.registers.a = 0b1100;
+    execution.bus().write(0x10, 0b1010);
+
+    execution.tsb(0x10);
+    assert_eq!(execution.bus().read(0x10), 0b1110);
+    assert_eq!(
+        execution.registers.flags & StatusRegister::ZERO,
+        StatusRegister::empty()
+    );
+
+    execution.trb(0x10);
+    assert_eq!(execution.bus().read(0x10), 0b0010);
+    assert_eq!(
+        execution.registers.flags & StatusRegister::ZERO,
+        StatusRegister::empty()
+    );
+}
+
+#[test]
+fn push_pull() {
+    let mut execution = CmosExecution {
+        bus: TestBus::new(),
+        registers: Registers::new(),
+    };
+
+    execution.registers.s = 0xff;
+    execution.registers.x = 0x42;
+
+    execution.phx();
+    assert_eq!(execution.registers.s, 0xfe);
+    assert_eq!(execution.bus().read(0x01ff), 0x42);
+
+    execution.ply();
+    assert_eq!(execution.registers.s, 0xff);
+    assert_eq!(execution.registers.y, 0x42);
+}
+
+#[test]
+fn cmos_gated_on_nmos() {
+    let mut execution = TestExecution {
+        bus: TestBus::new(),
+        registers: Registers::new(),
+    };
+
+    execution.bus().write(0x1234, 0xff);
+    // STZ is a no-op on an NMOS engine.
+    execution.stz(0x1234);
+    assert_eq!(execution.bus().read(0x1234), 0xff);
+}
+
+#[test]
+fn reset_loads_vector() {
+    let mut execution = CmosExecution {
+        bus: TestBus::new(),
+        registers: Registers::new(),
+    };
+
+    execution.bus().write(RESET_VECTOR, 0x00);
+    execution.bus().write(RESET_VECTOR + 1, 0x80);
+
+    execution.reset();
+    assert_eq!(execution.registers.pc, 0x8000);
+}
+
+#[test]
+fn step_adc_imm() {
+    let mut execution = CmosExecution {
+        bus: TestBus::new(),
+        registers: Registers::new(),
+    };
+
+    // ADC #$05
+    execution.bus().write(0x8000, 0x69);
+    execution.bus().write(0x8001, 0x05);
+    execution.registers.pc = 0x8000;
+
+    execution.step();
+    assert_eq!(execution.registers.a, 0x05);
+    assert_eq!(execution.registers.pc, 0x8002);
+}
+
+#[test]
+fn step_bra() {
+    let mut execution = CmosExecution {
+        bus: TestBus::new(),
+        registers: Registers::new(),
+    };
+
+    // BRA +4
+    execution.bus().write(0x8000, 0x80);
+    execution.bus().write(0x8001, 0x04);
+    execution.registers.pc = 0x8000;
+
+    execution.step();
+    assert_eq!(execution.registers.pc, 0x8006);
+}
+
+#[test]
+fn step_cycles() {
+    let mut execution = CmosExecution {
+        bus: TestBus::new(),
+        registers: Registers::new(),
+    };
+
+    // ADC #$05 is a flat 2 cycles.
+    execution.bus().write(0x8000, 0x69);
+    execution.bus().write(0x8001, 0x05);
+    execution.registers.pc = 0x8000;
+    assert_eq!(execution.step(), 2);
+
+    // ADC $80FF,X with X crossing a page boundary costs 4 + 1 cycles.
+    execution.bus().write(0x9000, 0x7d);
+    execution.bus().write(0x9001, 0xff);
+    execution.bus().write(0x9002, 0x80);
+    execution.registers.x = 0x02;
+    execution.registers.pc = 0x9000;
+    assert_eq!(execution.step(), 5);
+
+    // A taken BRA that crosses a page costs 2 + 1 + 1 cycles.
+    execution.bus().write(0x80fd, 0x80);
+    execution.bus().write(0x80fe, 0x10);
+    execution.registers.pc = 0x80fd;
+    assert_eq!(execution.step(), 4);
+}
+
+#[test]
+fn load_and_run_until_trap() {
+    let mut execution = CmosExecution {
+        bus: TestBus::new(),
+        registers: Registers::new(),
+    };
+
+    // ADC #$01 ; BRA *  (an infinite self-loop trap)
+    let program = [0x69, 0x01, 0x80, 0xfe];
+    execution.bus().load(0x0400, &program);
+    execution.bus().write(RESET_VECTOR, 0x00);
+    execution.bus().write(RESET_VECTOR + 1, 0x04);
+
+    execution.reset();
+    let trap = execution.run_until_trap();
+    assert_eq!(trap, 0x0402);
+    assert_eq!(execution.registers.a, 0x01);
+}
+
 #[test]
 fn adc_common() {
     let mut execution = TestExecution {
@@ -201,6 +436,39 @@ fn adc_common() {
     );
 }
 
+#[test]
+fn adc_decimal() {
+    let mut execution = TestExecution {
+        bus: TestBus::new(),
+        registers: Registers::new(),
+    };
+
+    execution.registers.flags.insert(StatusRegister::DECIMAL);
+
+    // 0x09 + 0x01 = 0x10 in BCD
+    execution.registers.a = 0x09;
+    execution.adc_common(0x01);
+    assert_eq!(execution.registers.a, 0x10);
+    assert_eq!(
+        execution.registers.flags & StatusRegister::CARRY,
+        StatusRegister::empty()
+    );
+
+    // 0x99 + 0x01 = 0x00 with carry in BCD
+    execution.registers.a = 0x99;
+    execution.adc_common(0x01);
+    assert_eq!(execution.registers.a, 0x00);
+    assert_eq!(
+        execution.registers.flags & StatusRegister::CARRY,
+        StatusRegister::CARRY
+    );
+    // Z is set from the binary sum (0x99 + 0x01 + 0 = 0x9a), which is non-zero.
+    assert_eq!(
+        execution.registers.flags & StatusRegister::ZERO,
+        StatusRegister::empty()
+    );
+}
+
 #[test]
 fn adc_imm() {
     let mut execution = TestExecution {